@@ -32,6 +32,7 @@ from src.models.messages import (
     TaskCompletion,
     UserProfile,
 )
+from src.engine.sts import curve_energy_pct, level_to_percent, percent_to_level
 from src.agents.protocols import create_chat_protocol
 
 logger = logging.getLogger(__name__)
@@ -91,11 +92,6 @@ def _get_redis() -> redis.Redis:
 
 # ── Energy Inference ─────────────────────────────────────────────────────
 
-def _get_time_based_energy(hour: int) -> int:
-    """Get baseline energy from circadian curve for given hour."""
-    return _energy_curve[hour % 24]
-
-
 def _get_velocity_adjustment(r: redis.Redis) -> tuple[int, int]:
     """Compute energy adjustment from recent task completion velocity.
 
@@ -174,7 +170,6 @@ def _get_user_reported(r: redis.Redis) -> tuple[int | None, float]:
 def _compute_energy(r: redis.Redis) -> EnergyLevel:
     """Compute current energy level from all signal sources."""
     now = datetime.now(timezone.utc)
-    hour = now.hour
 
     # Check user-reported first (highest priority)
     user_level, age = _get_user_reported(r)
@@ -182,18 +177,19 @@ def _compute_energy(r: redis.Redis) -> EnergyLevel:
         # Confidence decays linearly from 0.9 to 0.5 over the decay window
         decay_factor = 1.0 - (age / USER_REPORTED_DECAY_SECONDS)
         confidence = 0.5 + 0.4 * decay_factor
+        level = max(1, min(5, user_level))
         return EnergyLevel(
-            level=max(1, min(5, user_level)),
+            level=level,
             confidence=round(confidence, 2),
             source="user_reported",
+            percent=level_to_percent(level),
         )
 
-    # Time-based baseline
-    base_level = _get_time_based_energy(hour)
-
-    # Velocity adjustment
+    # Time-based baseline plus velocity adjustment; the 1-5 level is derived
+    # from the percent so callers on either scale agree
     velocity_adj, completion_count = _get_velocity_adjustment(r)
-    final_level = max(1, min(5, base_level + velocity_adj))
+    percent = curve_energy_pct(_energy_curve, now) + level_to_percent(velocity_adj)
+    percent = round(max(level_to_percent(1), min(level_to_percent(5), percent)), 1)
 
     # Confidence depends on data quality
     if _has_profiler_curve and completion_count >= 3:
@@ -210,9 +206,10 @@ def _compute_energy(r: redis.Redis) -> EnergyLevel:
         source = "time_based"
 
     return EnergyLevel(
-        level=final_level,
+        level=percent_to_level(percent),
         confidence=round(confidence, 2),
         source=source,
+        percent=percent,
     )
 
 
@@ -220,6 +217,7 @@ def _cache_energy(energy: EnergyLevel, r: redis.Redis) -> None:
     """Cache the latest computed energy in Redis for other services to read."""
     r.set(CACHED_ENERGY_KEY, json.dumps({
         "level": energy.level,
+        "percent": energy.percent,
         "confidence": energy.confidence,
         "source": energy.source,
         "timestamp": datetime.now(timezone.utc).isoformat(),
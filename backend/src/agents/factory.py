@@ -40,6 +40,7 @@ from src.config.settings import (
     GHOST_WORKER_ADDRESS,
     PROFILER_AGENT_ADDRESS,
     REMINDER_AGENT_ADDRESS,
+    KERNEL_EVENT_LOG,
     REDIS_URL,
     SENTINEL_POLL_INTERVAL,
     CALENDAR_LOOKAHEAD_HOURS,
@@ -71,10 +72,17 @@ from src.models.messages import (
     VoiceCommand,
 )
 from src.models.task import Task, TaskStatus
-from src.engine.lts import plan_day, replan_remaining
+from src.engine.lts import enqueue_task, plan_day, refresh_sts, replan_remaining
 from src.engine.mts import handle_disruption
-from src.engine.sts import ShortTermScheduler
+from src.engine.sts import (
+    DependencyCycleError,
+    ShortTermScheduler,
+    curve_energy_pct,
+    level_to_percent,
+    percent_to_level,
+)
 from src.engine.task_buffer import get_active_tasks, store_task
+from src.engine.event_sink import JsonlEventSink, NullEventSink, record_disruption
 from src.engine.disruption_classifier import (
     classify_severity,
     calculate_freed_minutes,
@@ -298,6 +306,7 @@ def create_scheduler_kernel(port: int = 8002) -> Agent:
         "sts": ShortTermScheduler(),
         "current_energy": DEFAULT_ENERGY,
         "peak_hours": list(DEFAULT_PEAK_HOURS),
+        "event_sink": JsonlEventSink(KERNEL_EVENT_LOG) if KERNEL_EVENT_LOG else NullEventSink(),
     }
 
     def _get_redis_client() -> redis.Redis:
@@ -306,7 +315,7 @@ def create_scheduler_kernel(port: int = 8002) -> Agent:
     def _build_schedule_message(trigger: str) -> UpdatedSchedule:
         sts = _state["sts"]
         energy = _state["current_energy"]
-        ordered = sts.get_ordered_schedule(energy.level)
+        ordered = sts.get_ordered_schedule_pct(energy.percent or level_to_percent(energy.level))
         schedule = []
         for task in ordered:
             schedule.append({
@@ -315,6 +324,7 @@ def create_scheduler_kernel(port: int = 8002) -> Agent:
                 "priority": task.priority,
                 "estimated_duration": task.estimated_duration,
                 "energy_cost": task.energy_cost,
+                "energy_cost_pct": task.energy_pct,
                 "status": task.status,
                 "deadline": task.deadline,
             })
@@ -329,9 +339,8 @@ def create_scheduler_kernel(port: int = 8002) -> Agent:
     async def on_startup(ctx: Context):
         logger.info("Scheduler Kernel started. Address: %s", agent.address)
         r = _get_redis_client()
-        active = get_active_tasks(r)
+        active = refresh_sts(_state["sts"], r)
         if active:
-            _state["sts"].enqueue_batch(active)
             logger.info("Loaded %d active tasks into STS", len(active))
 
     @agent.on_message(DisruptionEvent)
@@ -348,8 +357,9 @@ def create_scheduler_kernel(port: int = 8002) -> Agent:
         except Exception:
             logger.debug("Energy Monitor unavailable, using cached level")
 
+        delegation_msgs: List[DelegationTask] = []
         if event.recommended_action == "reschedule_all":
-            tasks, new_sts = plan_day(peak_hours=_state["peak_hours"], r=r)
+            tasks, new_sts, _ = plan_day(peak_hours=_state["peak_hours"], r=r)
             _state["sts"] = new_sts
         else:
             result = handle_disruption(
@@ -360,10 +370,15 @@ def create_scheduler_kernel(port: int = 8002) -> Agent:
                 r=r,
             )
             logger.info("MTS result: %s", result.summary)
+            from src.agents.scheduler_kernel import _dispatch_delegations
+            delegation_msgs = await _dispatch_delegations(
+                ctx, result.delegated + _state["sts"].get_delegation_queue(),
+            )
+
+        refresh_sts(_state["sts"], r)
+        schedule_msg = _build_schedule_message("disruption")
 
-        active = get_active_tasks(r)
-        _state["sts"].reorder(active)
-        _build_schedule_message("disruption")
+        record_disruption(_state["event_sink"], event, schedule_msg, delegation_msgs)
 
     @agent.on_message(EnergyLevel)
     async def handle_energy_update(ctx: Context, sender: str, energy: EnergyLevel):
@@ -386,7 +401,16 @@ def create_scheduler_kernel(port: int = 8002) -> Agent:
         r = _get_redis_client()
         if req.action == "plan_day":
             hours = req.payload.get("available_hours", 8)
-            tasks, new_sts = plan_day(available_hours=hours, peak_hours=_state["peak_hours"], r=r)
+            cap = req.payload.get("max_tasks_per_day")
+            try:
+                tasks, new_sts, _ = plan_day(
+                    available_hours=hours,
+                    max_tasks_per_day=int(cap) if cap is not None else None,
+                    peak_hours=_state["peak_hours"], r=r,
+                )
+            except (TypeError, ValueError) as exc:
+                logger.warning("plan_day rejected: %s", exc)
+                return
             _state["sts"] = new_sts
         elif req.action == "reoptimize":
             replan_remaining(_state["sts"], _state["current_energy"].level, r)
@@ -394,9 +418,14 @@ def create_scheduler_kernel(port: int = 8002) -> Agent:
             task_data = req.payload.get("task")
             if task_data:
                 task = Task.from_dict(task_data)
+                try:
+                    _state["sts"].check_dependency_cycle(task)
+                except DependencyCycleError as exc:
+                    logger.warning("Task rejected: %s", exc)
+                    return
                 task.status = TaskStatus.ACTIVE
                 store_task(task, r)
-                _state["sts"].enqueue(task)
+                enqueue_task(_state["sts"], task, r)
 
     async def _chat_handler(ctx: Context, sender: str, text: str) -> str:
         counts = _state["sts"].queue_counts()
@@ -454,7 +483,6 @@ def create_energy_monitor(port: int = 8003) -> Agent:
 
     def _compute_energy(r: redis.Redis) -> EnergyLevel:
         now = datetime.now(timezone.utc)
-        hour = now.hour
 
         # Check user-reported first
         reported = r.get("energy:user_reported")
@@ -464,20 +492,27 @@ def create_energy_monitor(port: int = 8003) -> Agent:
             if age <= USER_REPORTED_DECAY:
                 decay_factor = 1.0 - (age / USER_REPORTED_DECAY)
                 confidence = 0.5 + 0.4 * decay_factor
+                level = max(1, min(5, int(reported)))
                 return EnergyLevel(
-                    level=max(1, min(5, int(reported))),
+                    level=level,
                     confidence=round(confidence, 2),
                     source="user_reported",
+                    percent=level_to_percent(level),
                 )
 
-        base_level = _state["energy_curve"][hour % 24]
+        # Derive the 1-5 level from the percent so both scales agree
+        percent = round(curve_energy_pct(_state["energy_curve"], now), 1)
         confidence = 0.4
         source = "time_based"
-        return EnergyLevel(level=base_level, confidence=confidence, source=source)
+        return EnergyLevel(
+            level=percent_to_level(percent), confidence=confidence, source=source,
+            percent=percent,
+        )
 
     def _cache_energy(energy: EnergyLevel, r: redis.Redis) -> None:
         r.set("energy:current", json.dumps({
             "level": energy.level,
+            "percent": energy.percent,
             "confidence": energy.confidence,
             "source": energy.source,
             "timestamp": datetime.now(timezone.utc).isoformat(),
@@ -18,6 +18,7 @@ from src.config.settings import (
     SCHEDULER_KERNEL_SEED,
     ENERGY_MONITOR_ADDRESS,
     GHOST_WORKER_ADDRESS,
+    KERNEL_EVENT_LOG,
     REDIS_URL,
 )
 from src.models.messages import (
@@ -29,10 +30,16 @@ from src.models.messages import (
     DelegationTask,
 )
 from src.models.task import Task, TaskStatus
-from src.engine.lts import plan_day, replan_remaining
+from src.engine.lts import enqueue_task, plan_day, refresh_sts, replan_remaining
 from src.engine.mts import handle_disruption
-from src.engine.sts import ShortTermScheduler
-from src.engine.task_buffer import get_active_tasks, store_task
+from src.engine.sts import DependencyCycleError, ShortTermScheduler, level_to_percent
+from src.engine.task_buffer import store_task
+from src.engine.event_sink import (
+    EventSink,
+    JsonlEventSink,
+    NullEventSink,
+    record_disruption,
+)
 from src.agents.protocols import create_chat_protocol
 
 logger = logging.getLogger(__name__)
@@ -62,6 +69,9 @@ agent = Agent(
 _sts = ShortTermScheduler()
 _current_energy = DEFAULT_ENERGY
 _peak_hours = DEFAULT_PEAK_HOURS
+_event_sink: EventSink = (
+    JsonlEventSink(KERNEL_EVENT_LOG) if KERNEL_EVENT_LOG else NullEventSink()
+)
 
 
 def _get_redis() -> redis.Redis:
@@ -70,7 +80,8 @@ def _get_redis() -> redis.Redis:
 
 def _build_schedule_message(trigger: str) -> UpdatedSchedule:
     """Build an UpdatedSchedule message from current STS state."""
-    ordered = _sts.get_ordered_schedule(_current_energy.level)
+    energy_pct = _current_energy.percent or level_to_percent(_current_energy.level)
+    ordered = _sts.get_ordered_schedule_pct(energy_pct)
     schedule = []
     for task in ordered:
         schedule.append({
@@ -79,6 +90,7 @@ def _build_schedule_message(trigger: str) -> UpdatedSchedule:
             "priority": task.priority,
             "estimated_duration": task.estimated_duration,
             "energy_cost": task.energy_cost,
+            "energy_cost_pct": task.energy_pct,
             "status": task.status,
             "deadline": task.deadline,
         })
@@ -109,6 +121,24 @@ def _build_delegation_tasks(tasks: list[Task]) -> list[DelegationTask]:
     return delegations
 
 
+async def _dispatch_delegations(ctx: Context, tasks: list[Task]) -> list[DelegationTask]:
+    """Build the kernel's delegation messages and send them to GhostWorker.
+
+    MTS results and the STS delegation queue can both carry the same
+    auto-delegated task, so tasks are de-duplicated by id first. The full
+    list is returned for the event log even when no GhostWorker address is
+    configured and nothing is sent.
+    """
+    unique = list({t.task_id: t for t in tasks}.values())
+    delegations = _build_delegation_tasks(unique)
+    if not GHOST_WORKER_ADDRESS:
+        return delegations
+    for d in delegations:
+        logger.info(f"Delegating task {d.task_id} to GhostWorker")
+        await ctx.send(GHOST_WORKER_ADDRESS, d)
+    return delegations
+
+
 # ── Message Handlers ─────────────────────────────────────────────────────
 
 @agent.on_message(DisruptionEvent)
@@ -135,11 +165,12 @@ async def handle_disruption_event(ctx: Context, sender: str, event: DisruptionEv
         logger.debug("Energy Monitor unavailable, using cached energy level")
 
     energy = _current_energy.level
+    delegation_msgs: list[DelegationTask] = []
 
     if event.recommended_action == "reschedule_all":
         # Critical: full replan
         logger.info("CRITICAL disruption — replanning entire day")
-        tasks, _sts = plan_day(
+        tasks, _sts, _ = plan_day(
             energy_curve=None,
             peak_hours=_peak_hours,
             r=r,
@@ -156,21 +187,17 @@ async def handle_disruption_event(ctx: Context, sender: str, event: DisruptionEv
         logger.info(f"MTS result: {result.summary}")
 
         # Handle delegations — send to GhostWorker agent
-        delegated = result.delegated + _sts.get_delegation_queue()
-        if delegated:
-            delegation_msgs = _build_delegation_tasks(delegated)
-            for d in delegation_msgs:
-                logger.info(f"Delegating task {d.task_id} to GhostWorker")
-                if GHOST_WORKER_ADDRESS:
-                    await ctx.send(GHOST_WORKER_ADDRESS, d)
+        delegation_msgs = await _dispatch_delegations(
+            ctx, result.delegated + _sts.get_delegation_queue(),
+        )
 
-    # STS reorder with current energy
-    active = get_active_tasks(r)
-    _sts.reorder(active)
+    # STS reorder with current energy; in-progress work stays pinned first
+    refresh_sts(_sts, r)
 
     # Emit updated schedule
     schedule_msg = _build_schedule_message("disruption")
     logger.info(f"Updated schedule: {len(schedule_msg.schedule)} tasks")
+    record_disruption(_event_sink, event, schedule_msg, delegation_msgs)
     # In production: push via WebSocket to frontend
     # await ctx.send(FRONTEND_ADDRESS, schedule_msg)
 
@@ -200,12 +227,18 @@ async def handle_schedule_request(ctx: Context, sender: str, req: ScheduleReques
 
     if req.action == "plan_day":
         hours = req.payload.get("available_hours", 8)
-        tasks, _sts = plan_day(
-            available_hours=hours,
-            peak_hours=_peak_hours,
-            r=r,
-        )
-        logger.info(f"Daily plan created: {len(tasks)} tasks")
+        cap = req.payload.get("max_tasks_per_day")
+        try:
+            tasks, _sts, carryover = plan_day(
+                available_hours=hours,
+                max_tasks_per_day=int(cap) if cap is not None else None,
+                peak_hours=_peak_hours,
+                r=r,
+            )
+        except (TypeError, ValueError) as exc:
+            logger.warning(f"plan_day rejected: {exc}")
+            return
+        logger.info(f"Daily plan created: {len(tasks)} tasks, {len(carryover)} carried over")
 
     elif req.action == "reoptimize":
         replan_remaining(_sts, _current_energy.level, r)
@@ -215,10 +248,15 @@ async def handle_schedule_request(ctx: Context, sender: str, req: ScheduleReques
         task_data = req.payload.get("task")
         if task_data:
             task = Task.from_dict(task_data)
-            task.status = TaskStatus.ACTIVE
-            store_task(task, r)
-            _sts.enqueue(task)
-            logger.info(f"Task added: {task.title}")
+            try:
+                _sts.check_dependency_cycle(task)
+            except DependencyCycleError as exc:
+                logger.warning(f"Task rejected: {exc}")
+            else:
+                task.status = TaskStatus.ACTIVE
+                store_task(task, r)
+                enqueue_task(_sts, task, r)
+                logger.info(f"Task added: {task.title}")
 
     schedule_msg = _build_schedule_message(req.action)
     # In production: push to frontend
@@ -233,9 +271,8 @@ async def on_startup(ctx: Context):
     logger.info(f"Energy level: {_current_energy.level}/5")
 
     r = _get_redis()
-    active = get_active_tasks(r)
+    active = refresh_sts(_sts, r)
     if active:
-        _sts.enqueue_batch(active)
         logger.info(f"Loaded {len(active)} active tasks into STS")
     else:
         logger.info("No active tasks found. Run daily planning to populate.")
@@ -72,6 +72,9 @@ TASK_BUCKET_COUNT: int = int(os.getenv("TASK_BUCKET_COUNT", "16"))
 DEFAULT_AVAILABLE_HOURS: int = int(os.getenv("DEFAULT_AVAILABLE_HOURS", "8"))
 DEFAULT_ENERGY_LEVEL: int = int(os.getenv("DEFAULT_ENERGY_LEVEL", "3"))
 
+# Optional JSONL log of Scheduler Kernel inputs/outputs (empty = disabled)
+KERNEL_EVENT_LOG: str = os.getenv("KERNEL_EVENT_LOG", "")
+
 # ── Composio Configuration ──────────────────────────────────────────────
 
 COMPOSIO_API_KEY: str = os.getenv("COMPOSIO_API_KEY", "")
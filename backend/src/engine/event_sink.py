@@ -0,0 +1,72 @@
+"""Event Sink — append-only log of Scheduler Kernel inputs and outputs.
+
+Lets external consumers (iOS bridge, replay tooling) follow what the kernel
+did without subscribing to agent messages. Each record is one JSON line:
+  {"kind": ..., "timestamp": ..., "payload": {...}}
+
+Kinds written per disruption: disruption_event, updated_schedule,
+delegation_queue.
+"""
+
+from __future__ import annotations
+
+import json
+import logging
+from datetime import datetime, timezone
+from pathlib import Path
+from typing import Protocol
+
+from src.models.messages import DelegationTask, DisruptionEvent, UpdatedSchedule
+
+logger = logging.getLogger(__name__)
+
+
+class EventSink(Protocol):
+    """Anything that can accept a kernel event record."""
+
+    def write(self, kind: str, payload: dict) -> None: ...
+
+
+class NullEventSink:
+    """Default sink — discards everything."""
+
+    def write(self, kind: str, payload: dict) -> None:
+        pass
+
+
+class JsonlEventSink:
+    """Appends one JSON object per line to a file."""
+
+    def __init__(self, path: str | Path):
+        self.path = Path(path)
+
+    def write(self, kind: str, payload: dict) -> None:
+        record = {
+            "kind": kind,
+            "timestamp": datetime.now(timezone.utc).isoformat(),
+            "payload": payload,
+        }
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        with self.path.open("a") as f:
+            f.write(json.dumps(record) + "\n")
+
+
+def record_disruption(
+    sink: EventSink,
+    event: DisruptionEvent,
+    schedule: UpdatedSchedule,
+    delegations: list[DelegationTask],
+) -> None:
+    """Write the incoming disruption, resulting schedule, and delegation queue.
+
+    Sink failures are logged, never raised — the event log must not break
+    scheduling.
+    """
+    try:
+        sink.write("disruption_event", json.loads(event.json()))
+        sink.write("updated_schedule", json.loads(schedule.json()))
+        sink.write("delegation_queue", {
+            "tasks": [json.loads(d.json()) for d in delegations],
+        })
+    except Exception as exc:
+        logger.warning(f"Event sink write failed: {exc}")
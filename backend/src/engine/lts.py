@@ -18,8 +18,14 @@ import redis
 
 from src.config.settings import REDIS_URL
 from src.models.task import Task, TaskStatus
-from src.engine.task_buffer import get_backlog_tasks, get_active_tasks, store_task
-from src.engine.sts import ShortTermScheduler
+from src.engine.task_buffer import (
+    get_active_tasks,
+    get_backlog_tasks,
+    get_task,
+    get_unfinished_ids,
+    store_task,
+)
+from src.engine.sts import ShortTermScheduler, dependency_cycle_ids, in_progress_task_id
 
 logger = logging.getLogger(__name__)
 
@@ -42,13 +48,26 @@ def plan_day(
     energy_curve: list[int] | None = None,
     peak_hours: list[int] | None = None,
     estimation_bias: float = 1.0,
+    max_tasks_per_day: int | None = None,
     r: redis.Redis | None = None,
-) -> tuple[list[Task], ShortTermScheduler]:
+) -> tuple[list[Task], ShortTermScheduler, list[Task]]:
     """Generate today's schedule from the backlog.
 
+    max_tasks_per_day caps how many tasks get pulled in — only the top-N
+    scoring tasks are activated; the rest stay in the backlog and are
+    returned as carryover. A negative cap raises ValueError.
+
+    A task is planned together with its unfinished backlog dependencies, or
+    not at all. Tasks on a depends_on cycle stay in the backlog.
+
     Returns:
-        Tuple of (scheduled_tasks, sts_instance) ready for execution.
+        Tuple of (scheduled_tasks, sts_instance, carryover): the STS is ready
+        for execution; carryover lists the backlog tasks left for another
+        day, highest score first.
     """
+    if max_tasks_per_day is not None and max_tasks_per_day < 0:
+        raise ValueError(f"max_tasks_per_day must be >= 0, got {max_tasks_per_day}")
+
     r = r or _get_redis()
     energy_curve = energy_curve or DEFAULT_ENERGY_CURVE
     peak_hours = peak_hours or [9, 10, 14, 15]
@@ -56,11 +75,18 @@ def plan_day(
     backlog = get_backlog_tasks(r)
     if not backlog:
         logger.info("LTS: No tasks in backlog")
-        return [], ShortTermScheduler()
+        return [], ShortTermScheduler(), []
+
+    # Tasks on a dependency cycle can never run; leave them in the backlog
+    cyclic_ids = dependency_cycle_ids(backlog + get_active_tasks(r))
+    cyclic = [t for t in backlog if t.task_id in cyclic_ids]
+    if cyclic:
+        logger.warning(f"LTS: circular depends_on, not planning: {sorted(cyclic_ids)}")
+        backlog = [t for t in backlog if t.task_id not in cyclic_ids]
 
-    # Apply estimation bias correction
+    # Apply estimation bias correction (keeping Task's >= 1 minute invariant)
     for task in backlog:
-        task.estimated_duration = int(task.estimated_duration * estimation_bias)
+        task.estimated_duration = max(1, int(task.estimated_duration * estimation_bias))
 
     # Score and sort tasks for selection
     scored = _score_tasks(backlog, peak_hours)
@@ -68,31 +94,84 @@ def plan_day(
 
     # Bin-pack into available time
     available_minutes = available_hours * 60
+    backlog_by_id = {t.task_id: t for t in backlog}
     selected = []
+    chosen: set[str] = set()
     used_minutes = 0
 
     for task, score in scored:
-        if used_minutes + task.estimated_duration > available_minutes:
+        if max_tasks_per_day is not None and len(selected) >= max_tasks_per_day:
+            break
+        if task.task_id in chosen:
+            continue
+        group = _with_dependencies(task, backlog_by_id, chosen, r)
+        if group is None:
+            continue
+        group_minutes = sum(t.estimated_duration for t in group)
+        if max_tasks_per_day is not None and len(selected) + len(group) > max_tasks_per_day:
+            continue
+        if used_minutes + group_minutes > available_minutes:
             # Try to fit shorter tasks
             continue
-        selected.append(task)
-        used_minutes += task.estimated_duration
+        selected.extend(group)
+        chosen.update(t.task_id for t in group)
+        used_minutes += group_minutes
 
     # Activate selected tasks
     for task in selected:
         task.status = TaskStatus.ACTIVE
         store_task(task, r)
 
-    # Build STS schedule
+    # Build STS schedule; dependencies already active elsewhere still block
     sts = ShortTermScheduler()
-    sts.enqueue_batch(selected)
+    deps = {d for t in selected for d in t.depends_on} - chosen
+    sts.reorder(selected, outside_pending=get_unfinished_ids(deps, r))
 
     logger.info(
         f"LTS: Planned {len(selected)} tasks ({used_minutes}min) "
         f"from {len(backlog)} backlog tasks"
     )
 
-    return selected, sts
+    carryover = [t for t, _ in scored if t.task_id not in chosen] + cyclic
+    return selected, sts, carryover
+
+
+def _with_dependencies(
+    task: Task,
+    backlog_by_id: dict[str, Task],
+    chosen: set[str],
+    r: redis.Redis,
+) -> list[Task] | None:
+    """`task` plus the unfinished backlog tasks it depends on, dependencies first.
+
+    Returns None if a dependency is unfinished but can't be planned today
+    (swapped out, delegated, or on a cycle) — the task then stays in the
+    backlog rather than being scheduled blocked.
+    """
+    group: list[Task] = []
+    seen: set[str] = set()
+
+    def visit(t: Task) -> bool:
+        for dep_id in t.depends_on:
+            if dep_id in seen or dep_id in chosen:
+                continue
+            seen.add(dep_id)
+            dep = backlog_by_id.get(dep_id)
+            if dep is None:
+                stored = get_task(dep_id, r)
+                if stored and stored.status not in (
+                    TaskStatus.COMPLETED, TaskStatus.ACTIVE, TaskStatus.IN_PROGRESS,
+                ):
+                    return False
+                continue
+            if not visit(dep):
+                return False
+            group.append(dep)
+        return True
+
+    if not visit(task):
+        return None
+    return group + [task]
 
 
 def _score_tasks(tasks: list[Task], peak_hours: list[int]) -> list[tuple[Task, float]]:
@@ -138,6 +217,35 @@ def _score_tasks(tasks: list[Task], peak_hours: list[int]) -> list[tuple[Task, f
     return scored
 
 
+def refresh_sts(sts: ShortTermScheduler, r: redis.Redis | None = None) -> list[Task]:
+    """Rebuild the STS from the active tasks in Redis and return them.
+
+    The in-progress task stays pinned, and dependencies that are unfinished
+    but not active (backlog, swapped out, delegated) keep their dependents
+    blocked.
+    """
+    r = r or _get_redis()
+    active = get_active_tasks(r)
+    deps = {d for t in active for d in t.depends_on}
+    sts.reorder(
+        active,
+        current_task_id=in_progress_task_id(active),
+        outside_pending=get_unfinished_ids(deps, r),
+    )
+    return active
+
+
+def enqueue_task(sts: ShortTermScheduler, task: Task, r: redis.Redis | None = None) -> None:
+    """Enqueue a newly added task without rebuilding the STS.
+
+    Dependencies that are unfinished but not active (backlog, swapped out,
+    delegated) keep the task blocked, as they would after refresh_sts.
+    """
+    r = r or _get_redis()
+    sts.enqueue(task)
+    sts.add_outside_pending(get_unfinished_ids(set(task.depends_on), r))
+
+
 def replan_remaining(
     sts: ShortTermScheduler,
     energy_level: int = 3,
@@ -145,9 +253,8 @@ def replan_remaining(
 ) -> list[Task]:
     """Re-plan remaining active tasks (e.g., after a disruption changes the landscape).
 
-    Pulls the current STS queue, re-scores, and rebuilds.
+    Pulls the current STS queue, re-scores, and rebuilds. An in-progress
+    task stays pinned at the front.
     """
-    r = r or _get_redis()
-    active = get_active_tasks(r)
-    sts.reorder(active)
+    refresh_sts(sts, r)
     return sts.get_ordered_schedule(energy_level)
@@ -19,10 +19,10 @@ from src.models.task import Task, TaskStatus
 from src.engine.task_buffer import (
     find_swap_candidates,
     find_swap_out_candidates,
-    get_active_tasks,
     store_task,
 )
 from src.engine.sts import ShortTermScheduler
+from src.engine.lts import enqueue_task, refresh_sts
 
 logger = logging.getLogger(__name__)
 
@@ -69,7 +69,7 @@ def handle_swap_in(
         task.status = TaskStatus.ACTIVE
         store_task(task, r)
         if sts:
-            sts.enqueue(task)
+            enqueue_task(sts, task, r)
         swapped_in.append(task)
         remaining_minutes -= task.estimated_duration
         logger.info(f"SWAP-IN: {task.title} ({task.estimated_duration}min, E={task.energy_cost})")
@@ -95,7 +95,10 @@ def handle_swap_out(
     Selects lowest-priority, least-urgent tasks to free the needed time.
     """
     r = r or _get_redis()
-    candidates = find_swap_out_candidates(lost_minutes, r)
+    # Never swap out the task the user is currently working on
+    current = sts.get_current() if sts else None
+    exclude = {current.task_id} if current else set()
+    candidates = find_swap_out_candidates(lost_minutes, r, exclude_ids=exclude)
 
     swapped_out = []
     for task in candidates:
@@ -139,8 +142,7 @@ def handle_disruption(
     else:
         # No time change — might still need reordering
         if sts:
-            active = get_active_tasks(r or _get_redis())
-            sts.reorder(active)
+            refresh_sts(sts, r or _get_redis())
         return SwapResult(
             swapped_in=[], swapped_out=[], delegated=[],
             summary="No time change. Reordered active schedule.",
@@ -7,16 +7,86 @@ Modified Multilevel Feedback Queue adapted for human tasks:
   P3 (Background): Nice-to-haves, low-energy fillers, delegatable
 
 Energy constraint: never schedule energy_cost > energy_level.
+
+Dependencies: a task never runs before the tasks in its depends_on that
+are unfinished — queued, in progress, or reported by the caller as pending
+outside the STS (backlog, swapped out, delegated). Only completed or unknown
+dependencies count as satisfied. enqueue rejects a task that would close a
+cycle; reorder logs and skips it instead, so stored data never breaks a
+replan.
+
+Energy can be given on the coarse 1-5 scale or the finer 0-100 percent
+scale (level N = N * 20%). The 1-5 methods are thin wrappers over the
+percent ones, so both make the same decisions at the level boundaries.
 """
 
 from __future__ import annotations
 
 import heapq
+import logging
 from dataclasses import dataclass, field
 from datetime import datetime, timezone
 from typing import Optional
 
-from src.models.task import Task, Priority, TaskStatus
+from src.models.task import Task, Priority, TaskStatus, ENERGY_PCT_PER_LEVEL
+
+logger = logging.getLogger(__name__)
+
+
+class DependencyCycleError(ValueError):
+    """Raised when enqueueing a task would create a depends_on cycle."""
+
+
+def level_to_percent(energy_level: int) -> float:
+    """Map a 1-5 energy level onto the 0-100 scale."""
+    return float(energy_level * ENERGY_PCT_PER_LEVEL)
+
+
+def percent_to_level(energy_pct: float) -> int:
+    """Map a 0-100 energy percentage to the highest 1-5 level it fully covers."""
+    return max(1, min(5, int(energy_pct // ENERGY_PCT_PER_LEVEL)))
+
+
+def dependency_cycle_ids(tasks: list[Task]) -> set[str]:
+    """IDs of the tasks that sit on a depends_on cycle among `tasks`."""
+    by_id = {t.task_id: t for t in tasks}
+    on_cycle: set[str] = set()
+    for task in tasks:
+        stack = list(task.depends_on)
+        seen: set[str] = set()
+        while stack:
+            dep_id = stack.pop()
+            if dep_id == task.task_id:
+                on_cycle.add(task.task_id)
+                break
+            if dep_id in seen or dep_id not in by_id:
+                continue
+            seen.add(dep_id)
+            stack.extend(by_id[dep_id].depends_on)
+    return on_cycle
+
+
+def curve_energy_pct(energy_curve: list[int], now: datetime) -> float:
+    """Time-of-day energy on the 0-100 scale.
+
+    Interpolates between the hourly 1-5 curve points, so 09:30 between a 4
+    and a 5 reads 90% rather than jumping at the hour.
+    """
+    here = energy_curve[now.hour % 24]
+    nxt = energy_curve[(now.hour + 1) % 24]
+    return level_to_percent(here + (nxt - here) * now.minute / 60)
+
+
+def in_progress_task_id(tasks: list[Task]) -> Optional[str]:
+    """ID of the task the user is currently working on, if any.
+
+    If several tasks are IN_PROGRESS, the most recently updated one wins
+    (ties broken by task_id), so the pick doesn't depend on Redis set order.
+    """
+    started = [t for t in tasks if t.status == TaskStatus.IN_PROGRESS]
+    if not started:
+        return None
+    return max(started, key=lambda t: (t.updated_at, t.task_id)).task_id
 
 
 @dataclass(order=True)
@@ -41,9 +111,16 @@ class ShortTermScheduler:
         self._current_task: Optional[Task] = None
         # Tasks delegated to GhostWorker
         self._delegation_queue: list[Task] = []
+        # Unfinished tasks outside the STS that queued tasks may depend on
+        self._outside_pending: set[str] = set()
 
     def enqueue(self, task: Task) -> None:
-        """Add a task to the appropriate priority queue."""
+        """Add a task to the appropriate priority queue.
+
+        Raises DependencyCycleError if the task's depends_on closes a cycle
+        with tasks already queued.
+        """
+        self.check_dependency_cycle(task)
         priority = self._classify_priority(task)
         task.priority = priority
         # Sort within priority level by deadline urgency (higher urgency = lower sort_key)
@@ -61,15 +138,25 @@ class ShortTermScheduler:
         Scans from P0 to P3. Skips tasks whose energy_cost > energy_level.
         If energy is low and only P3 tasks remain, auto-delegates them.
         """
+        return self.dequeue_pct(level_to_percent(energy_level))
+
+    def dequeue_pct(self, energy_pct: float = 100.0) -> Optional[Task]:
+        """Like dequeue(), with energy on the 0-100 scale.
+
+        Skips tasks whose energy_pct > energy_pct, and tasks whose
+        dependencies are still pending.
+        """
+        pending = self._pending_ids()
         for priority in (Priority.P0_URGENT, Priority.P1_IMPORTANT,
                          Priority.P2_NORMAL, Priority.P3_BACKGROUND):
             queue = self._queues[priority]
-            # Find first task that fits energy budget
+            # Find first task that fits energy budget and is unblocked
             skipped = []
             result = None
             while queue:
                 entry = heapq.heappop(queue)
-                if entry.task.energy_cost <= energy_level:
+                blocked = any(d in pending for d in entry.task.depends_on)
+                if entry.task.energy_pct <= energy_pct and not blocked:
                     result = entry.task
                     # Put skipped entries back
                     for s in skipped:
@@ -146,28 +233,201 @@ class ShortTermScheduler:
     def get_ordered_schedule(self, energy_level: int = 5) -> list[Task]:
         """Return all queued tasks in execution order (non-destructive).
 
-        Respects energy constraints: tasks above energy budget are placed
-        at the end.
+        The in-progress current task, if any, always comes first.
+        Respects energy constraints: tasks above energy budget, and tasks
+        waiting on an unfinished dependency outside the STS, are placed at
+        the end.
         """
+        return self.get_ordered_schedule_pct(level_to_percent(energy_level))
+
+    def get_ordered_schedule_pct(self, energy_pct: float = 100.0) -> list[Task]:
+        """Like get_ordered_schedule(), with energy on the 0-100 scale."""
         schedule = []
         deferred = []
+        pinned = self._current_task
+        if pinned and pinned.status != TaskStatus.IN_PROGRESS:
+            pinned = None
 
         for priority in (Priority.P0_URGENT, Priority.P1_IMPORTANT,
                          Priority.P2_NORMAL, Priority.P3_BACKGROUND):
             entries = sorted(self._queues[priority])
             for entry in entries:
-                if entry.task.energy_cost <= energy_level:
+                if pinned and entry.task.task_id == pinned.task_id:
+                    continue
+                blocked = any(d in self._outside_pending for d in entry.task.depends_on)
+                if entry.task.energy_pct <= energy_pct and not blocked:
                     schedule.append(entry.task)
                 else:
                     deferred.append(entry.task)
 
-        return schedule + deferred
+        ordered = self._respect_dependencies(schedule + deferred)
+        return [pinned] + ordered if pinned else ordered
+
+    def explain_schedule(self, energy_level: int = 5) -> list[tuple[Task, str]]:
+        """get_ordered_schedule() paired with why each task sits where it does.
 
-    def reorder(self, tasks: list[Task]) -> None:
-        """Clear and rebuild all queues from a list of tasks."""
+        Each reason names the factor that put the task ahead of the one
+        after it: pinned current task, dependency, energy skip, waiting on
+        an unfinished dependency, priority band, or deadline urgency.
+        """
+        return self.explain_schedule_pct(level_to_percent(energy_level))
+
+    def explain_schedule_pct(self, energy_pct: float = 100.0) -> list[tuple[Task, str]]:
+        """Like explain_schedule(), with energy on the 0-100 scale."""
+        ordered = self.get_ordered_schedule_pct(energy_pct)
+        explained = []
+        for i, task in enumerate(ordered):
+            explained.append((task, self._explain(task, ordered[i + 1:], energy_pct)))
+        return explained
+
+    def _explain(self, task: Task, later: list[Task], energy_pct: float) -> str:
+        """Deciding factor for `task` being ordered before the tasks in `later`.
+
+        A task with an unfinished dependency is explained by that wait, not
+        by its band. A later task depending on `task` wins over the
+        comparison with the immediate successor, whose position may be down
+        to other factors.
+        """
+        band = Priority(task.priority).name
+        if task is self._current_task and task.status == TaskStatus.IN_PROGRESS:
+            return "in progress — pinned to the front"
+        if task.energy_pct > energy_pct:
+            return (f"energy skip: needs {task.energy_pct:.0f}% energy, "
+                    f"{energy_pct:.0f}% available")
+        pending = self._pending_ids()
+        waiting_on = next((d for d in task.depends_on if d in pending), None)
+        if waiting_on is not None:
+            return f"waiting on dependency {waiting_on}"
+        if not later:
+            return f"{band} band, last in schedule"
+        dependent = next((t for t in later if task.task_id in t.depends_on), None)
+        if dependent is not None:
+            return f"dependency of {dependent.task_id}"
+        nxt = later[0]
+        if nxt.energy_pct > energy_pct:
+            return f"fits energy budget; {nxt.task_id} is deferred"
+        if task.priority < nxt.priority:
+            return f"{band} band outranks {Priority(nxt.priority).name}"
+        if task.deadline_urgency > nxt.deadline_urgency:
+            return (f"{band} band, higher deadline urgency than {nxt.task_id} "
+                    f"({task.deadline_urgency:.1f} vs {nxt.deadline_urgency:.1f})")
+        return f"{band} band, queued ahead of {nxt.task_id}"
+
+    def reorder(
+        self,
+        tasks: list[Task],
+        current_task_id: str | None = None,
+        outside_pending: set[str] | None = None,
+    ) -> None:
+        """Clear and rebuild all queues from a list of tasks.
+
+        If current_task_id is given, that task (the fresh object from `tasks`)
+        is pinned as the current task instead of being re-queued, so a replan
+        never reorders work the user is mid-way through. Otherwise the old
+        current task is cleared: it was finished, paused or swapped out, and
+        if it is still in `tasks` it is queued like any other task.
+
+        outside_pending lists unfinished tasks that are not in `tasks`
+        (backlog, swapped out, delegated); dependents wait for them. A task
+        that would close a depends_on cycle is logged and left out — reorder
+        never raises on stored data.
+        """
         for q in self._queues.values():
             q.clear()
-        self.enqueue_batch(tasks)
+        self._outside_pending = set(outside_pending or ())
+
+        self._current_task = None
+        if current_task_id:
+            for task in tasks:
+                if task.task_id == current_task_id:
+                    self.set_current(task)
+            tasks = [t for t in tasks if t.task_id != current_task_id]
+
+        for task in tasks:
+            try:
+                self.enqueue(task)
+            except DependencyCycleError as exc:
+                logger.warning(f"STS: leaving {task.task_id} out of the queues: {exc}")
+
+    def add_outside_pending(self, task_ids: set[str]) -> None:
+        """Mark unfinished tasks outside the STS as pending.
+
+        Used when a task is enqueued between rebuilds: its dependencies in
+        the backlog, swapped out or delegated keep it blocked. IDs the STS
+        already tracks are skipped.
+        """
+        self._outside_pending |= set(task_ids) - self._pending_ids()
+
+    def _queued_tasks(self) -> dict[str, Task]:
+        return {
+            entry.task.task_id: entry.task
+            for queue in self._queues.values()
+            for entry in queue
+        }
+
+    def _pending_ids(self) -> set[str]:
+        """IDs of tasks not yet done: everything queued, the in-progress task,
+        and unfinished tasks outside the STS."""
+        pending = set(self._queued_tasks()) | self._outside_pending
+        current = self._current_task
+        if current and current.status == TaskStatus.IN_PROGRESS:
+            pending.add(current.task_id)
+        return pending
+
+    def check_dependency_cycle(self, task: Task) -> None:
+        """Raise DependencyCycleError if `task` would close a depends_on cycle.
+
+        Walks depends_on from `task` through queued tasks looking for `task`.
+        Callers that persist a task check this before writing it.
+        """
+        if not task.depends_on:
+            return
+        queued = self._queued_tasks()
+        stack = list(task.depends_on)
+        seen: set[str] = set()
+        while stack:
+            dep_id = stack.pop()
+            if dep_id == task.task_id:
+                raise DependencyCycleError(
+                    f"Task {task.task_id} has a circular depends_on chain"
+                )
+            if dep_id in seen or dep_id not in queued:
+                continue
+            seen.add(dep_id)
+            stack.extend(queued[dep_id].depends_on)
+
+    @staticmethod
+    def _respect_dependencies(ordered: list[Task]) -> list[Task]:
+        """Stable topological pass: move each task after its dependencies.
+
+        Each step emits the earliest task (in the given order) whose
+        dependencies within the list are already emitted, so tasks without
+        dependencies keep their relative order. Queues built by enqueue and
+        reorder are acyclic; if a cycle slips in anyway, the rest is appended
+        in its given order rather than failing a read.
+        """
+        if not any(t.depends_on for t in ordered):
+            return ordered
+
+        ids = {t.task_id for t in ordered}
+        emitted: set[str] = set()
+        result: list[Task] = []
+        waiting = list(ordered)
+        while waiting:
+            for i, task in enumerate(waiting):
+                if all(d in emitted or d not in ids for d in task.depends_on):
+                    result.append(task)
+                    emitted.add(task.task_id)
+                    del waiting[i]
+                    break
+            else:
+                logger.warning(
+                    "STS: circular depends_on among: "
+                    + ", ".join(t.task_id for t in waiting)
+                )
+                result.extend(waiting)
+                break
+        return result
 
     def _classify_priority(self, task: Task) -> int:
         """Auto-classify task priority based on deadline and attributes."""
@@ -188,7 +448,7 @@ class ShortTermScheduler:
             except (ValueError, TypeError):
                 pass
 
-        if task.cognitive_load <= 1 and task.energy_cost <= 1:
+        if task.cognitive_load <= 1 and task.energy_pct <= ENERGY_PCT_PER_LEVEL:
             return Priority.P3_BACKGROUND
 
         return task.priority
@@ -81,6 +81,21 @@ def get_active_tasks(r: redis.Redis | None = None) -> list[Task]:
     return tasks
 
 
+def get_unfinished_ids(task_ids: set[str], r: redis.Redis | None = None) -> set[str]:
+    """The subset of task_ids that are stored and not yet completed.
+
+    Used to tell the STS which dependencies still block their dependents;
+    unknown ids (deleted tasks) count as finished.
+    """
+    r = r or _get_redis()
+    unfinished = set()
+    for tid in task_ids:
+        task = Task.from_redis(r, tid)
+        if task and task.status != TaskStatus.COMPLETED:
+            unfinished.add(tid)
+    return unfinished
+
+
 def find_swap_candidates(
     available_minutes: int,
     energy_level: int,
@@ -131,17 +146,22 @@ def find_swap_candidates(
 def find_swap_out_candidates(
     minutes_needed: int,
     r: redis.Redis | None = None,
+    exclude_ids: set[str] | None = None,
 ) -> list[Task]:
     """Find active tasks that can be swapped out to free time.
 
     Returns tasks sorted by priority (lowest/P3 first) then by deadline
-    urgency (least urgent first).
+    urgency (least urgent first). Tasks in exclude_ids are never chosen.
     """
     r = r or _get_redis()
     active = get_active_tasks(r)
+    exclude_ids = exclude_ids or set()
 
     # Can't swap out in-progress tasks
-    candidates = [t for t in active if t.status == TaskStatus.ACTIVE]
+    candidates = [
+        t for t in active
+        if t.status == TaskStatus.ACTIVE and t.task_id not in exclude_ids
+    ]
 
     # Sort: lowest priority first, then least urgent deadline
     candidates.sort(key=lambda t: (-t.priority, t.deadline_urgency))
@@ -47,6 +47,7 @@ class EnergyLevel(Model):
     level: int               # 1-5
     confidence: float        # 0.0-1.0
     source: str              # inferred | user_reported | time_based
+    percent: float = 0.0     # finer 0-100 level; 0 = level * 20
 
 
 class EnergyQuery(Model):
@@ -20,6 +20,9 @@ BUCKET_PREFIX = "bucket:"
 BACKLOG_KEY = "task:backlog"
 ACTIVE_KEY = "task:active"
 
+# 1-5 energy scale ↔ 0-100 percent scale (level 5 = 100%)
+ENERGY_PCT_PER_LEVEL = 20
+
 
 class Priority(IntEnum):
     P0_URGENT = 0      # Hard deadlines within 2 hours, external dependencies
@@ -44,17 +47,37 @@ class Task:
     description: str = ""
     priority: int = Priority.P2_NORMAL
     energy_cost: int = 3            # 1-5
+    energy_cost_pct: float = 0.0    # 0-100 finer cost; 0 = derive from energy_cost
     estimated_duration: int = 30    # minutes
     deadline: str = ""              # ISO 8601
     preferred_start: str = ""       # ISO 8601
     status: str = TaskStatus.BACKLOG
     tags: list = field(default_factory=list)
+    depends_on: list = field(default_factory=list)  # task_ids that must run first
     task_type: str = "general"      # general | email_reply | slack_message | ...
     cognitive_load: int = 3         # 1-5
     progress_notes: str = ""        # state save for preemption
     created_at: str = field(default_factory=lambda: datetime.now(timezone.utc).isoformat())
     updated_at: str = field(default_factory=lambda: datetime.now(timezone.utc).isoformat())
 
+    def __post_init__(self):
+        """Clamp numeric fields into the ranges the schedulers rely on.
+
+        Invariants: priority P0-P3, energy_cost and cognitive_load 1-5,
+        energy_cost_pct 0-100, estimated_duration >= 1 minute. An energy_cost
+        above 5 would otherwise never pass the STS energy check, and an
+        out-of-range priority has no STS queue. A set energy_cost_pct also
+        sets energy_cost to the smallest level covering it, so 1-5 consumers
+        (swap-in, schedule messages) agree with the percent scale.
+        """
+        self.priority = max(Priority.P0_URGENT, min(Priority.P3_BACKGROUND, self.priority))
+        self.energy_cost = max(1, min(5, self.energy_cost))
+        self.cognitive_load = max(1, min(5, self.cognitive_load))
+        self.energy_cost_pct = max(0.0, min(100.0, self.energy_cost_pct))
+        if self.energy_cost_pct > 0:
+            self.energy_cost = max(1, math.ceil(self.energy_cost_pct / ENERGY_PCT_PER_LEVEL))
+        self.estimated_duration = max(1, self.estimated_duration)
+
     @property
     def deadline_urgency(self) -> float:
         """Inverse of time-to-deadline normalized to 0-10. Higher = more urgent."""
@@ -69,6 +92,13 @@ class Task:
         except (ValueError, TypeError):
             return 0.0
 
+    @property
+    def energy_pct(self) -> float:
+        """Energy cost on the 0-100 scale. Level N on the 1-5 scale = N * 20."""
+        if self.energy_cost_pct > 0:
+            return self.energy_cost_pct
+        return self.energy_cost * ENERGY_PCT_PER_LEVEL
+
     @property
     def execution_time_score(self) -> float:
         """Normalized execution time score 0-10. Shorter tasks score higher (SJF-inspired)."""
@@ -102,11 +132,13 @@ class Task:
     def to_dict(self) -> dict:
         d = asdict(self)
         d["tags"] = json.dumps(d["tags"])
+        d["depends_on"] = json.dumps(d["depends_on"])
         # Ensure enums are stored as plain ints
         d["priority"] = int(self.priority)
         d["energy_cost"] = int(self.energy_cost)
         d["cognitive_load"] = int(self.cognitive_load)
         d["estimated_duration"] = int(self.estimated_duration)
+        d["energy_cost_pct"] = float(self.energy_cost_pct)
         return d
 
     @classmethod
@@ -114,6 +146,8 @@ class Task:
         data = dict(data)  # copy
         if isinstance(data.get("tags"), str):
             data["tags"] = json.loads(data["tags"])
+        if isinstance(data.get("depends_on"), str):
+            data["depends_on"] = json.loads(data["depends_on"])
         # Ensure int fields — handle enum repr strings like '<Priority.P1_IMPORTANT: 1>'
         for int_field in ("priority", "energy_cost", "estimated_duration", "cognitive_load"):
             if int_field in data and isinstance(data[int_field], str):
@@ -122,6 +156,8 @@ class Task:
                 if ":" in val and val.startswith("<"):
                     val = val.split(":")[-1].strip().rstrip(">")
                 data[int_field] = int(val)
+        if isinstance(data.get("energy_cost_pct"), str):
+            data["energy_cost_pct"] = float(data["energy_cost_pct"])
         return cls(**{k: v for k, v in data.items() if k in cls.__dataclass_fields__})
 
     def to_redis(self, r: redis.Redis) -> None:
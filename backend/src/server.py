@@ -22,14 +22,14 @@ from typing import Any, Optional
 import redis
 from fastapi import FastAPI, WebSocket, WebSocketDisconnect, Query
 from fastapi.middleware.cors import CORSMiddleware
-from pydantic import BaseModel
+from pydantic import BaseModel, Field
 
 from src.config.settings import REDIS_URL, TASK_BUCKET_COUNT, ELEVENLABS_API_KEY, ELEVENLABS_AGENT_ID
 from src.models.task import Task, TaskStatus
 from src.engine.task_buffer import get_active_tasks, get_backlog_tasks, store_task
-from src.engine.lts import plan_day
+from src.engine.lts import enqueue_task, plan_day, refresh_sts
 from src.engine.mts import handle_disruption
-from src.engine.sts import ShortTermScheduler
+from src.engine.sts import DependencyCycleError, ShortTermScheduler
 from src.engine.disruption_classifier import (
     classify_severity,
     calculate_freed_minutes,
@@ -237,8 +237,7 @@ async def websocket_endpoint(ws: WebSocket):
                             task.status = TaskStatus.COMPLETED
                             task.to_redis(r)
                             r.srem("task:active", task_id)
-                            active = get_active_tasks(r)
-                            _sts.reorder(active)
+                            active = refresh_sts(_sts, r)
                             frontend_tasks = [_task_to_frontend(t) for t in active]
                             update_msg = _build_ws_message("updated_schedule", {
                                 "tasks": frontend_tasks, "swaps": [],
@@ -314,6 +313,7 @@ async def get_schedule():
 
 class PlanDayRequest(BaseModel):
     available_hours: int = 8
+    max_tasks_per_day: int | None = Field(default=None, ge=0)
 
 
 @app.post("/api/schedule/plan-day")
@@ -322,8 +322,9 @@ async def trigger_plan_day(req: PlanDayRequest):
     global _sts
     r = _get_redis()
 
-    tasks, _sts = plan_day(
+    tasks, _sts, carryover = plan_day(
         available_hours=req.available_hours,
+        max_tasks_per_day=req.max_tasks_per_day,
         peak_hours=_peak_hours,
         r=r,
     )
@@ -339,7 +340,11 @@ async def trigger_plan_day(req: PlanDayRequest):
     })
     await manager.broadcast(msg)
 
-    return {"planned": len(tasks), "tasks": frontend_tasks}
+    return {
+        "planned": len(tasks),
+        "tasks": frontend_tasks,
+        "carryover": [_task_to_frontend(t) for t in carryover],
+    }
 
 
 class DisruptionRequest(BaseModel):
@@ -412,7 +417,7 @@ async def simulate_disruption(req: DisruptionRequest):
     # Step 2: Scheduler Kernel runs MTS
     result = None
     if action == "reschedule_all":
-        tasks, _sts = plan_day(peak_hours=_peak_hours, r=r)
+        tasks, _sts, _ = plan_day(peak_hours=_peak_hours, r=r)
     else:
         result = handle_disruption(
             freed_minutes=freed_minutes,
@@ -422,9 +427,8 @@ async def simulate_disruption(req: DisruptionRequest):
             r=r,
         )
 
-        # Rebuild STS with current active tasks
-        active = get_active_tasks(r)
-        _sts.reorder(active)
+        # Rebuild STS with current active tasks, keeping in-progress work first
+        refresh_sts(_sts, r)
 
     # Step 3: Build updated schedule with swap info
     active = get_active_tasks(r)
@@ -580,8 +584,7 @@ async def complete_task(task_id: str):
     r.srem("task:active", task_id)
 
     # Rebuild STS and broadcast
-    active = get_active_tasks(r)
-    _sts.reorder(active)
+    active = refresh_sts(_sts, r)
     frontend_tasks = [_task_to_frontend(t) for t in active]
     msg = _build_ws_message("updated_schedule", {
         "tasks": frontend_tasks,
@@ -627,11 +630,13 @@ class CreateTaskRequest(BaseModel):
     description: str = ""
     priority: int = 2
     energy_cost: int = 3
+    energy_cost_pct: float = 0.0
     estimated_duration: int = 30
     deadline: str = ""
     preferred_start: str = ""
     task_type: str = "general"
     cognitive_load: int = 3
+    depends_on: list[str] = []
 
 
 @app.post("/api/tasks")
@@ -645,15 +650,21 @@ async def create_task(req: CreateTaskRequest):
         description=req.description,
         priority=req.priority,
         energy_cost=req.energy_cost,
+        energy_cost_pct=req.energy_cost_pct,
         estimated_duration=req.estimated_duration,
         deadline=req.deadline,
         preferred_start=req.preferred_start,
         task_type=req.task_type,
         cognitive_load=req.cognitive_load,
+        depends_on=req.depends_on,
         status=TaskStatus.ACTIVE,
     )
+    try:
+        _sts.check_dependency_cycle(task)
+    except DependencyCycleError as exc:
+        return {"error": str(exc), "task_id": task_id}
     store_task(task, r)
-    _sts.enqueue(task)
+    enqueue_task(_sts, task, r)
 
     frontend_task = _task_to_frontend(task)
 
@@ -697,8 +708,7 @@ async def delete_task(task_id: str):
     Task.delete_from_redis(r, task_id)
 
     # Rebuild STS from remaining active tasks
-    active = get_active_tasks(r)
-    _sts.reorder(active)
+    active = refresh_sts(_sts, r)
 
     msg = _build_ws_message("updated_schedule", {
         "tasks": [_task_to_frontend(t) for t in active],
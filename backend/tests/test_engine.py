@@ -164,6 +164,12 @@ class TestSTS:
         sts.enqueue(t)
         assert sts.dequeue(energy_level=1) is None
 
+    def test_clamped_task_is_dequeuable(self, sts):
+        """energy_cost=99 is clamped to 5, so full energy can still run it."""
+        t = self._make_task("huge", Priority.P1_IMPORTANT, energy_cost=99)
+        sts.enqueue(t)
+        assert sts.dequeue(energy_level=5).task_id == "huge"
+
     def test_auto_delegate_p3_low_energy(self, sts):
         """energy <= 2 delegates all P3 tasks."""
         p3a = self._make_task("p3a", Priority.P3_BACKGROUND, energy_cost=1)
@@ -244,6 +250,279 @@ class TestSTS:
         sts.reorder([t1, t2])
         assert sts.total_count == 2
 
+    def test_reorder_pins_current_task_first(self, sts):
+        """The designated current task stays first even when a P0 arrives."""
+        current = self._make_task("current", Priority.P3_BACKGROUND)
+        urgent = self._make_task("urgent", Priority.P0_URGENT)
+        normal = self._make_task("normal", Priority.P2_NORMAL)
+
+        sts.reorder([urgent, current, normal], current_task_id="current")
+
+        schedule = sts.get_ordered_schedule(energy_level=5)
+        assert [t.task_id for t in schedule] == ["current", "urgent", "normal"]
+        assert sts.get_current().status == TaskStatus.IN_PROGRESS
+        assert sts.total_count == 2  # pinned task is not re-queued
+
+    def test_reorder_clears_finished_current_task(self, sts):
+        """A current task absent from the new task list is no longer pinned."""
+        done = self._make_task("done", Priority.P2_NORMAL)
+        other = self._make_task("other", Priority.P2_NORMAL)
+        sts.set_current(done)
+
+        sts.reorder([other])
+
+        assert sts.get_current() is None
+        assert [t.task_id for t in sts.get_ordered_schedule()] == ["other"]
+
+    def test_reorder_unpins_paused_current_task(self, sts):
+        """A paused task (ACTIVE again in Redis) is queued, not kept pinned."""
+        a = self._make_task("a", Priority.P2_NORMAL)
+        sts.set_current(a)
+        paused = self._make_task("a", Priority.P2_NORMAL)  # fresh copy, ACTIVE
+        paused.status = TaskStatus.ACTIVE
+        urgent = self._make_task("u", Priority.P0_URGENT)
+
+        sts.reorder([paused, urgent], current_task_id=None)
+
+        schedule = sts.get_ordered_schedule(energy_level=5)
+        assert sts.get_current() is None
+        assert [t.task_id for t in schedule] == ["u", "a"]
+        assert schedule[1].status == TaskStatus.ACTIVE
+        assert sts.total_count == 2
+
+    def test_percent_path_matches_level_path_at_boundaries(self):
+        """dequeue(level) and dequeue_pct(level * 20) drain a mixed queue identically."""
+        def mixed():
+            tasks = [
+                self._make_task(f"{p.name}-e{cost}", p, energy_cost=cost)
+                for p in (Priority.P0_URGENT, Priority.P2_NORMAL, Priority.P3_BACKGROUND)
+                for cost in range(1, 6)
+            ]
+            # Percent costs sitting exactly on a level boundary
+            on_edge = self._make_task("edge-60", Priority.P1_IMPORTANT)
+            on_edge.energy_cost_pct = 60.0
+            return tasks + [on_edge]
+
+        for level in range(1, 6):
+            by_level = ShortTermScheduler()
+            by_pct = ShortTermScheduler()
+            by_level.enqueue_batch(mixed())
+            by_pct.enqueue_batch(mixed())
+            assert ([t.task_id for t in by_level.get_ordered_schedule(level)]
+                    == [t.task_id for t in by_pct.get_ordered_schedule_pct(level * 20)])
+
+            picked_level = [t.task_id for t in iter(lambda: by_level.dequeue(level), None)]
+            picked_pct = [t.task_id for t in iter(lambda: by_pct.dequeue_pct(level * 20), None)]
+            assert picked_level == picked_pct, level
+            assert len(picked_level) == 3 * level + (level >= 3)
+
+    def test_percent_energy_admits_finer_cost(self, sts):
+        """At 70% energy a 65% task runs, but a level-4 (80%) task does not."""
+        fine = self._make_task("fine", Priority.P1_IMPORTANT, energy_cost=4)
+        fine.energy_cost_pct = 65
+        coarse = self._make_task("coarse", Priority.P0_URGENT, energy_cost=4)
+        sts.enqueue(fine)
+        sts.enqueue(coarse)
+
+        assert sts.dequeue_pct(energy_pct=70).task_id == "fine"
+        assert sts.dequeue_pct(energy_pct=70) is None
+
+    def test_ordered_schedule_pct_defers_costly(self, sts):
+        cheap = self._make_task("cheap", Priority.P1_IMPORTANT, energy_cost=1)
+        expensive = self._make_task("exp", Priority.P0_URGENT, energy_cost=5)
+        sts.enqueue(cheap)
+        sts.enqueue(expensive)
+
+        schedule = sts.get_ordered_schedule_pct(energy_pct=40)
+        assert [t.task_id for t in schedule] == ["cheap", "exp"]
+
+    def test_explain_mentions_deadline_urgency(self, sts):
+        """Two same-band tasks differing only in deadline explain the urgency."""
+        now = datetime.now(timezone.utc)
+        soon = self._make_task("soon", Priority.P1_IMPORTANT,
+                               deadline=(now + timedelta(hours=3)).isoformat())
+        later = self._make_task("later", Priority.P1_IMPORTANT,
+                                deadline=(now + timedelta(hours=48)).isoformat())
+        sts.enqueue(later)
+        sts.enqueue(soon)
+
+        explained = sts.explain_schedule(energy_level=5)
+        assert [t.task_id for t, _ in explained] == ["soon", "later"]
+        assert "higher deadline urgency" in explained[0][1]
+
+    def test_explain_band_energy_and_pin(self, sts):
+        current = self._make_task("cur", Priority.P2_NORMAL)
+        current.status = TaskStatus.IN_PROGRESS
+        sts.set_current(current)
+        p0 = self._make_task("p0", Priority.P0_URGENT, energy_cost=1)
+        p1 = self._make_task("p1", Priority.P1_IMPORTANT, energy_cost=1)
+        heavy = self._make_task("heavy", Priority.P0_URGENT, energy_cost=5)
+        for t in (p0, p1, heavy):
+            sts.enqueue(t)
+
+        reasons = {t.task_id: why for t, why in sts.explain_schedule(energy_level=2)}
+        assert "pinned" in reasons["cur"]
+        assert "P0_URGENT band outranks P1_IMPORTANT" in reasons["p0"]
+        assert "deferred" in reasons["p1"]
+        assert reasons["heavy"].startswith("energy skip")
+
+    def test_explain_names_any_later_dependent(self, sts):
+        """C is explained by A depending on it, not by sitting ahead of D."""
+        c = self._make_task("c", Priority.P2_NORMAL)
+        d = self._make_task("d", Priority.P2_NORMAL)
+        a = self._make_task("a", Priority.P0_URGENT)
+        a.depends_on = ["c", "d"]
+        for t in (c, d, a):
+            sts.enqueue(t)
+
+        explained = sts.explain_schedule(energy_level=5)
+        assert [t.task_id for t, _ in explained] == ["c", "d", "a"]
+        reasons = {t.task_id: why for t, why in explained}
+        assert reasons["c"] == "dependency of a"
+        assert reasons["d"] == "dependency of a"
+        assert reasons["a"] == "waiting on dependency c"
+
+    def test_explain_blocked_task_matches_its_deferred_slot(self, sts):
+        """A task blocked from outside the STS is explained by the wait,
+        not by its band, and sits behind runnable work."""
+        pay = self._make_task("pay", Priority.P0_URGENT)
+        pay.depends_on = ["check"]
+        mail = self._make_task("mail", Priority.P2_NORMAL)
+        sts.reorder([pay, mail], outside_pending={"check"})
+
+        explained = sts.explain_schedule(energy_level=5)
+        assert [t.task_id for t, _ in explained] == ["mail", "pay"]
+        assert explained[1][1] == "waiting on dependency check"
+        assert sts.dequeue(energy_level=5).task_id == "mail"
+
+    def test_level_percent_mapping(self):
+        from src.engine.sts import level_to_percent, percent_to_level
+        assert [level_to_percent(n) for n in range(1, 6)] == [20, 40, 60, 80, 100]
+        assert percent_to_level(70) == 3
+        assert percent_to_level(100) == 5
+        assert percent_to_level(5) == 1
+        assert all(percent_to_level(level_to_percent(n)) == n for n in range(1, 6))
+
+    def test_curve_energy_pct_interpolates_within_the_hour(self):
+        from src.engine.sts import curve_energy_pct
+        curve = [3] * 24
+        curve[9], curve[10] = 4, 5
+        at = datetime(2026, 2, 15, 9, 0, tzinfo=timezone.utc)
+        assert curve_energy_pct(curve, at) == 80
+        assert curve_energy_pct(curve, at.replace(minute=30)) == pytest.approx(90)
+        assert curve_energy_pct(curve, at.replace(hour=10, minute=30)) == pytest.approx(80)
+
+    def test_energy_monitor_level_follows_percent(self, r):
+        """At 10:30 on a 5→4 curve the monitor reports 90%, so level 4 — not
+        the 10:00 curve value — and both scales defer the same tasks."""
+        from src.agents import energy_monitor as monitor
+        curve = [3] * 24
+        curve[10], curve[11] = 5, 4
+        with (
+            patch.object(monitor, "_energy_curve", curve),
+            patch.object(monitor, "_get_velocity_adjustment", return_value=(0, 0)),
+            patch.object(monitor, "datetime") as mock_dt,
+        ):
+            mock_dt.now.return_value = datetime(2026, 2, 15, 10, 30, tzinfo=timezone.utc)
+            energy = monitor._compute_energy(r)
+        assert energy.percent == pytest.approx(90)
+        assert energy.level == 4
+
+    def test_dependency_orders_pay_after_check_balance(self, sts):
+        """A P0 'pay' task still waits for its P2 'check balance' dependency."""
+        check = self._make_task("check", Priority.P2_NORMAL)
+        pay = self._make_task("pay", Priority.P0_URGENT)
+        pay.depends_on = ["check"]
+        sts.enqueue(pay)
+        sts.enqueue(check)
+
+        assert [t.task_id for t in sts.get_ordered_schedule()] == ["check", "pay"]
+        assert sts.dequeue(energy_level=5).task_id == "check"
+        assert sts.dequeue(energy_level=5).task_id == "pay"
+
+    def test_dependency_on_unqueued_task_is_satisfied(self, sts):
+        """Dependencies not in the STS (e.g. already completed) don't block."""
+        pay = self._make_task("pay", Priority.P0_URGENT)
+        pay.depends_on = ["done-elsewhere"]
+        sts.enqueue(pay)
+        assert sts.dequeue(energy_level=5).task_id == "pay"
+
+    def test_dependency_pending_outside_sts_blocks(self, sts):
+        """A dependency still in the backlog blocks even though it isn't queued."""
+        pay = self._make_task("pay", Priority.P0_URGENT)
+        pay.depends_on = ["check"]
+        sts.reorder([pay], outside_pending={"check"})
+        assert sts.dequeue(energy_level=5) is None
+
+    def test_dependency_pending_outside_sts_defers_in_schedule(self, sts):
+        """The ordered schedule agrees with dequeue: a task blocked from
+        outside the STS goes to the end, behind runnable work."""
+        pay = self._make_task("pay", Priority.P0_URGENT)
+        pay.depends_on = ["check"]
+        mail = self._make_task("mail", Priority.P2_NORMAL)
+        sts.reorder([pay, mail], outside_pending={"check"})
+
+        assert [t.task_id for t in sts.get_ordered_schedule(energy_level=5)] == ["mail", "pay"]
+        assert sts.dequeue(energy_level=5).task_id == "mail"
+        assert sts.dequeue(energy_level=5) is None
+
+    def test_reorder_skips_cyclic_task_instead_of_raising(self, sts):
+        a = self._make_task("a", Priority.P2_NORMAL)
+        b = self._make_task("b", Priority.P2_NORMAL)
+        c = self._make_task("c", Priority.P1_IMPORTANT)
+        a.depends_on = ["b"]
+        b.depends_on = ["a"]
+
+        sts.reorder([a, b, c])
+
+        assert sts.total_count == 2
+        assert [t.task_id for t in sts.get_ordered_schedule()][0] == "c"
+
+    def test_dependency_on_in_progress_task_blocks(self, sts):
+        check = self._make_task("check", Priority.P2_NORMAL)
+        pay = self._make_task("pay", Priority.P0_URGENT)
+        pay.depends_on = ["check"]
+        sts.set_current(check)
+        sts.enqueue(pay)
+        assert sts.dequeue(energy_level=5) is None
+
+    def test_dependency_cycle_rejected(self, sts):
+        from src.engine.sts import DependencyCycleError
+        a = self._make_task("a", Priority.P2_NORMAL)
+        b = self._make_task("b", Priority.P2_NORMAL)
+        a.depends_on = ["b"]
+        b.depends_on = ["a"]
+        sts.enqueue(a)
+        with pytest.raises(DependencyCycleError):
+            sts.enqueue(b)
+        assert sts.total_count == 1
+
+    def test_self_dependency_rejected(self, sts):
+        from src.engine.sts import DependencyCycleError
+        a = self._make_task("a", Priority.P2_NORMAL)
+        a.depends_on = ["a"]
+        with pytest.raises(DependencyCycleError):
+            sts.enqueue(a)
+
+    def test_in_progress_task_id(self):
+        from src.engine.sts import in_progress_task_id
+        a = self._make_task("a", Priority.P2_NORMAL)
+        b = self._make_task("b", Priority.P2_NORMAL)
+        assert in_progress_task_id([a, b]) is None
+        b.status = TaskStatus.IN_PROGRESS
+        assert in_progress_task_id([a, b]) == "b"
+
+    def test_in_progress_task_id_picks_most_recently_started(self):
+        from src.engine.sts import in_progress_task_id
+        a = self._make_task("a", Priority.P2_NORMAL)
+        b = self._make_task("b", Priority.P2_NORMAL)
+        for t in (a, b):
+            t.status = TaskStatus.IN_PROGRESS
+        a.updated_at = "2026-02-15T09:00:00+00:00"
+        b.updated_at = "2026-02-15T10:00:00+00:00"
+        assert in_progress_task_id([a, b]) == "b"
+        assert in_progress_task_id([b, a]) == "b"
+
 
 # ═══════════════════════════════════════════════════════════════════════════
 # Task Buffer (Redis-backed)
@@ -360,13 +639,13 @@ class TestLTS:
 
     def test_plan_day_empty_backlog(self, r):
         from src.engine.lts import plan_day
-        tasks, sts = plan_day(r=r)
+        tasks, sts, _ = plan_day(r=r)
         assert tasks == []
         assert sts.total_count == 0
 
     def test_plan_day_selects_and_activates(self, r, sample_backlog):
         from src.engine.lts import plan_day
-        tasks, sts = plan_day(available_hours=8, r=r)
+        tasks, sts, _ = plan_day(available_hours=8, r=r)
         assert len(tasks) > 0
         for t in tasks:
             assert t.status == TaskStatus.ACTIVE
@@ -374,14 +653,14 @@ class TestLTS:
     def test_plan_day_respects_available_hours(self, r, sample_backlog):
         """Total planned duration should not exceed available_hours * 60."""
         from src.engine.lts import plan_day
-        tasks, sts = plan_day(available_hours=2, r=r)  # only 120 min
+        tasks, sts, _ = plan_day(available_hours=2, r=r)  # only 120 min
         total_min = sum(t.estimated_duration for t in tasks)
         assert total_min <= 120
 
     def test_plan_day_builds_sts(self, r, sample_backlog):
         """plan_day returns a populated STS instance."""
         from src.engine.lts import plan_day
-        tasks, sts = plan_day(available_hours=8, r=r)
+        tasks, sts, _ = plan_day(available_hours=8, r=r)
         assert sts.total_count == len(tasks)
 
     def test_plan_day_estimation_bias(self, r, make_task):
@@ -392,7 +671,7 @@ class TestLTS:
             t.to_redis(r)
 
         from src.engine.lts import plan_day
-        normal_tasks, _ = plan_day(available_hours=2, estimation_bias=1.0, r=r)
+        normal_tasks, _, _ = plan_day(available_hours=2, estimation_bias=1.0, r=r)
         normal_count = len(normal_tasks)
 
         # Reset tasks to backlog
@@ -400,10 +679,179 @@ class TestLTS:
             t.status = TaskStatus.BACKLOG
             t.to_redis(r)
 
-        biased_tasks, _ = plan_day(available_hours=2, estimation_bias=2.0, r=r)
+        biased_tasks, _, _ = plan_day(available_hours=2, estimation_bias=2.0, r=r)
         # With 2x bias, 30min tasks become 60min → fewer fit in 120min
         assert len(biased_tasks) <= normal_count
 
+    def test_plan_day_bias_keeps_duration_positive(self, r, make_task):
+        """A bias below 1 never shrinks a short task to 0 minutes."""
+        make_task(task_id="tiny", estimated_duration=1).to_redis(r)
+
+        from src.engine.lts import plan_day
+        tasks, _, _ = plan_day(available_hours=1, estimation_bias=0.5, r=r)
+        assert [t.estimated_duration for t in tasks] == [1]
+
+    def test_plan_day_max_tasks_cap(self, r, make_task):
+        """A cap of 3 activates exactly 3 tasks; the rest stay in the backlog."""
+        for i in range(6):
+            t = make_task(task_id=f"cap-{i}", estimated_duration=15)
+            t.to_redis(r)
+
+        from src.engine.lts import plan_day
+        from src.engine.task_buffer import get_backlog_tasks
+        tasks, sts, carryover = plan_day(available_hours=8, max_tasks_per_day=3, r=r)
+        assert len(tasks) == 3
+        assert sts.total_count == 3
+        assert len(carryover) == 3
+        assert {t.task_id for t in carryover} == {t.task_id for t in get_backlog_tasks(r)}
+        assert {t.task_id for t in tasks}.isdisjoint(t.task_id for t in carryover)
+
+    def test_plan_day_rejects_negative_cap(self, r, make_task):
+        make_task(task_id="neg").to_redis(r)
+
+        from src.engine.lts import plan_day
+        from src.engine.task_buffer import get_backlog_tasks
+        with pytest.raises(ValueError):
+            plan_day(available_hours=8, max_tasks_per_day=-1, r=r)
+        assert len(get_backlog_tasks(r)) == 1
+
+    @pytest.mark.asyncio
+    async def test_kernel_plan_day_coerces_or_rejects_cap(self, r, make_task):
+        """A cap from a message payload may be a string: "2" is used as 2,
+        "lots" is logged and rejected instead of raising in the handler."""
+        from unittest.mock import AsyncMock
+        from src.agents import scheduler_kernel as kernel
+        from src.engine.task_buffer import get_backlog_tasks
+        from src.models.messages import ScheduleRequest
+
+        def request(cap):
+            return ScheduleRequest(action="plan_day", payload={"max_tasks_per_day": cap})
+
+        for i in range(4):
+            make_task(task_id=f"msg-{i}", estimated_duration=15).to_redis(r)
+        with (
+            patch.object(kernel, "_get_redis", return_value=r),
+            patch.object(kernel, "_sts", ShortTermScheduler()),
+        ):
+            await kernel.handle_schedule_request(AsyncMock(), "agent1user", request("lots"))
+            assert len(get_backlog_tasks(r)) == 4
+
+            await kernel.handle_schedule_request(AsyncMock(), "agent1user", request("2"))
+            assert kernel._sts.total_count == 2
+        assert len(get_backlog_tasks(r)) == 2
+
+    def test_plan_day_pulls_in_backlog_dependency(self, r, make_task):
+        check = make_task(task_id="check", priority=Priority.P3_BACKGROUND)
+        pay = make_task(task_id="pay", priority=Priority.P0_URGENT, depends_on=["check"])
+        for t in (check, pay):
+            t.to_redis(r)
+
+        from src.engine.lts import plan_day
+        tasks, sts, _ = plan_day(available_hours=8, r=r)
+        assert {t.task_id for t in tasks} == {"check", "pay"}
+        assert sts.dequeue(energy_level=5).task_id == "check"
+
+    def test_plan_day_leaves_dependent_when_dependency_stays_in_backlog(self, r, make_task):
+        """If the dependency can't be planned, the dependent isn't either."""
+        check = make_task(task_id="check", priority=Priority.P3_BACKGROUND)
+        pay = make_task(task_id="pay", priority=Priority.P0_URGENT, depends_on=["check"])
+        for t in (check, pay):
+            t.to_redis(r)
+
+        from src.engine.lts import plan_day
+        from src.engine.task_buffer import get_backlog_tasks
+        tasks, sts, _ = plan_day(available_hours=8, max_tasks_per_day=1, r=r)
+        assert [t.task_id for t in tasks] == ["check"]
+        assert [t.task_id for t in get_backlog_tasks(r)] == ["pay"]
+
+        # Too little time for the dependency: neither is planned
+        Task.delete_from_redis(r, "check")
+        Task.delete_from_redis(r, "pay")
+        long_check = make_task(task_id="check", estimated_duration=120)
+        pay = make_task(task_id="pay", priority=Priority.P0_URGENT,
+                        estimated_duration=15, depends_on=["check"])
+        for t in (long_check, pay):
+            t.to_redis(r)
+        tasks, sts, _ = plan_day(available_hours=1, r=r)
+        assert tasks == []
+        assert sts.dequeue(energy_level=5) is None
+
+    def test_plan_day_skips_dependency_cycle(self, r, make_task):
+        """Tasks on a depends_on cycle stay in the backlog; nothing raises."""
+        a = make_task(task_id="a", depends_on=["b"])
+        b = make_task(task_id="b", depends_on=["a"])
+        ok = make_task(task_id="ok")
+        for t in (a, b, ok):
+            t.to_redis(r)
+
+        from src.engine.lts import plan_day
+        from src.engine.task_buffer import get_backlog_tasks
+        tasks, sts, _ = plan_day(available_hours=8, r=r)
+        assert [t.task_id for t in tasks] == ["ok"]
+        assert {t.task_id for t in get_backlog_tasks(r)} == {"a", "b"}
+
+    def test_replan_blocks_on_swapped_out_dependency(self, r, make_task):
+        """A dependency MTS swapped out still blocks its active dependent."""
+        check = make_task(task_id="check", status=TaskStatus.SWAPPED_OUT)
+        pay = make_task(task_id="pay", priority=Priority.P0_URGENT,
+                        status=TaskStatus.ACTIVE, depends_on=["check"])
+        for t in (check, pay):
+            t.to_redis(r)
+
+        from src.engine.lts import replan_remaining
+        sts = ShortTermScheduler()
+        replan_remaining(sts, energy_level=5, r=r)
+        assert sts.dequeue(energy_level=5) is None
+
+        check.status = TaskStatus.COMPLETED
+        check.to_redis(r)
+        replan_remaining(sts, energy_level=5, r=r)
+        assert sts.dequeue(energy_level=5).task_id == "pay"
+
+    def test_added_task_blocks_on_backlog_dependency(self, r, make_task):
+        """A task added after the last rebuild still waits for a dependency
+        that is sitting in the backlog."""
+        make_task(task_id="check").to_redis(r)
+
+        from src.engine.lts import enqueue_task, refresh_sts
+        sts = ShortTermScheduler()
+        refresh_sts(sts, r)
+        pay = make_task(task_id="pay", priority=Priority.P0_URGENT,
+                        status=TaskStatus.ACTIVE, depends_on=["check"])
+        pay.to_redis(r)
+        enqueue_task(sts, pay, r)
+        assert sts.dequeue(energy_level=5) is None
+
+    @pytest.mark.asyncio
+    async def test_kernel_add_task_blocks_on_backlog_dependency(self, r, make_task):
+        from unittest.mock import AsyncMock
+        from src.agents import scheduler_kernel as kernel
+        from src.models.messages import ScheduleRequest
+
+        make_task(task_id="check").to_redis(r)
+        pay = make_task(task_id="pay", priority=Priority.P0_URGENT, depends_on=["check"])
+        sts = ShortTermScheduler()
+        with (
+            patch.object(kernel, "_get_redis", return_value=r),
+            patch.object(kernel, "_sts", sts),
+        ):
+            await kernel.handle_schedule_request(
+                AsyncMock(), "agent1user",
+                ScheduleRequest(action="add_task", payload={"task": pay.to_dict()}),
+            )
+        assert sts.total_count == 1
+        assert sts.dequeue(energy_level=5) is None
+
+    def test_replan_survives_stored_dependency_cycle(self, r, make_task):
+        a = make_task(task_id="a", status=TaskStatus.ACTIVE, depends_on=["b"])
+        b = make_task(task_id="b", status=TaskStatus.ACTIVE, depends_on=["a"])
+        for t in (a, b):
+            t.to_redis(r)
+
+        from src.engine.lts import replan_remaining
+        schedule = replan_remaining(ShortTermScheduler(), energy_level=5, r=r)
+        assert len(schedule) == 1
+
 
 # ═══════════════════════════════════════════════════════════════════════════
 # MTS (Medium-Term Scheduler)
@@ -496,6 +944,23 @@ class TestMTS:
         delegated_ids = {t.task_id for t in result.delegated}
         assert "del-p3" in delegated_ids
 
+    def test_swap_out_skips_current_task(self, r, make_task):
+        """The STS current task is never swapped out, even if stored as ACTIVE."""
+        from src.engine.mts import handle_swap_out
+        sts = ShortTermScheduler()
+        current = make_task(task_id="curr", priority=Priority.P3_BACKGROUND,
+                            status=TaskStatus.ACTIVE, estimated_duration=60)
+        current.to_redis(r)
+        sts.set_current(current)
+        other = make_task(task_id="other", priority=Priority.P2_NORMAL,
+                          status=TaskStatus.ACTIVE, estimated_duration=60)
+        other.to_redis(r)
+
+        result = handle_swap_out(lost_minutes=30, energy_level=3, sts=sts, r=r)
+        ids = {t.task_id for t in result.swapped_out}
+        assert "curr" not in ids
+        assert "other" in ids
+
     def test_preemption(self, r, make_task):
         """Handle preemption: urgent task preempts current work."""
         from src.engine.mts import handle_preemption
@@ -510,3 +975,137 @@ class TestMTS:
         result = handle_preemption(urgent, energy_level=5, sts=sts, r=r)
         assert len(result.swapped_in) == 1
         assert result.swapped_in[0].task_id == "urg"
+
+
+# ═══════════════════════════════════════════════════════════════════════════
+# Event Sink (kernel event log)
+# ═══════════════════════════════════════════════════════════════════════════
+
+
+class TestEventSink:
+    def _disruption(self):
+        from src.models.messages import DisruptionEvent
+        return DisruptionEvent(
+            severity="major",
+            affected_task_ids=["t1"],
+            freed_minutes=-30,
+            recommended_action="swap_out",
+            context_summary="meeting_overrun from google_calendar: 30min lost.",
+        )
+
+    def _schedule(self):
+        from src.models.messages import UpdatedSchedule
+        return UpdatedSchedule(
+            schedule=[{"task_id": "t2", "title": "Review PRs"}],
+            swaps=[],
+            timestamp="2026-02-15T12:00:00Z",
+            trigger="disruption",
+        )
+
+    def _delegation(self):
+        from src.models.messages import DelegationTask
+        return DelegationTask(
+            task_id="bg-1",
+            task_type="email_reply",
+            context={"title": "Send thank-you emails"},
+            approval_required=True,
+            max_cost_fet=0.01,
+        )
+
+    def test_record_disruption_writes_three_json_lines(self, tmp_path):
+        import json
+        from src.engine.event_sink import JsonlEventSink, record_disruption
+        path = tmp_path / "kernel_events.jsonl"
+        sink = JsonlEventSink(path)
+
+        record_disruption(sink, self._disruption(), self._schedule(), [self._delegation()])
+
+        lines = path.read_text().splitlines()
+        assert len(lines) == 3
+        records = [json.loads(line) for line in lines]
+        assert [r["kind"] for r in records] == [
+            "disruption_event", "updated_schedule", "delegation_queue",
+        ]
+        assert all(r["timestamp"] for r in records)
+        assert records[0]["payload"]["severity"] == "major"
+        assert records[1]["payload"]["schedule"][0]["task_id"] == "t2"
+        assert records[2]["payload"]["tasks"][0]["task_id"] == "bg-1"
+
+    def test_jsonl_sink_appends(self, tmp_path):
+        from src.engine.event_sink import JsonlEventSink, record_disruption
+        path = tmp_path / "kernel_events.jsonl"
+        sink = JsonlEventSink(path)
+
+        record_disruption(sink, self._disruption(), self._schedule(), [])
+        record_disruption(sink, self._disruption(), self._schedule(), [])
+
+        assert len(path.read_text().splitlines()) == 6
+
+    def test_failing_sink_does_not_raise(self):
+        from src.engine.event_sink import record_disruption
+
+        class _BrokenSink:
+            def write(self, kind, payload):
+                raise OSError("disk full")
+
+        record_disruption(_BrokenSink(), self._disruption(), self._schedule(), [])
+
+    async def _run_kernel_disruption(self, r, make_task, tmp_path, ghost_address):
+        """Run one disruption through the kernel handler; return (records, ctx)."""
+        import json
+        from unittest.mock import AsyncMock
+        from src.agents import scheduler_kernel as kernel
+        from src.engine.event_sink import JsonlEventSink
+        from src.models.messages import EnergyLevel
+
+        work = make_task(task_id="work", priority=Priority.P1_IMPORTANT,
+                         status=TaskStatus.ACTIVE)
+        bg = make_task(task_id="bg", priority=Priority.P3_BACKGROUND,
+                       energy_cost=1, status=TaskStatus.ACTIVE)
+        for t in (work, bg):
+            t.to_redis(r)
+        sts = ShortTermScheduler()
+        sts.enqueue_batch([work, bg])
+        path = tmp_path / "kernel_events.jsonl"
+        ctx = AsyncMock()
+
+        with (
+            patch.object(kernel, "_get_redis", return_value=r),
+            patch.object(kernel, "_sts", sts),
+            patch.object(kernel, "_event_sink", JsonlEventSink(path)),
+            patch.object(kernel, "_current_energy",
+                         EnergyLevel(level=2, confidence=1.0, source="user_reported")),
+            patch.object(kernel, "GHOST_WORKER_ADDRESS", ghost_address),
+        ):
+            await kernel.handle_disruption_event(ctx, "agent1detector", self._disruption())
+
+        records = [json.loads(line) for line in path.read_text().splitlines()]
+        return records, ctx
+
+    @pytest.mark.asyncio
+    async def test_kernel_disruption_writes_three_json_lines(self, r, make_task, tmp_path):
+        """One disruption through the kernel handler logs three records,
+        and the delegation record lists what was sent to GhostWorker."""
+        from src.models.messages import DelegationTask
+
+        records, ctx = await self._run_kernel_disruption(r, make_task, tmp_path, "agent1ghost")
+        assert [rec["kind"] for rec in records] == [
+            "disruption_event", "updated_schedule", "delegation_queue",
+        ]
+        sent = [c.args[1] for c in ctx.send.await_args_list
+                if isinstance(c.args[1], DelegationTask)]
+        assert [d.task_id for d in sent] == ["bg"]
+        assert [d["task_id"] for d in records[2]["payload"]["tasks"]] == ["bg"]
+
+    @pytest.mark.asyncio
+    async def test_kernel_logs_delegations_without_ghost_address(self, r, make_task, tmp_path):
+        """With no GhostWorker configured nothing is sent, but the delegation
+        record still lists the kernel's delegation queue."""
+        from src.models.messages import DelegationTask
+
+        records, ctx = await self._run_kernel_disruption(r, make_task, tmp_path, "")
+        sent = [c.args[1] for c in ctx.send.await_args_list
+                if isinstance(c.args[1], DelegationTask)]
+        assert sent == []
+        assert records[2]["kind"] == "delegation_queue"
+        assert [d["task_id"] for d in records[2]["payload"]["tasks"]] == ["bg"]
@@ -212,3 +212,25 @@ class TestBacklogEndpoint:
         resp = await client.get("/api/backlog")
         assert resp.status_code == 200
         assert len(resp.json()["tasks"]) == 3
+
+
+# ═══════════════════════════════════════════════════════════════════════════
+# Task Creation
+# ═══════════════════════════════════════════════════════════════════════════
+
+
+class TestCreateTaskEndpoint:
+    @pytest.mark.asyncio
+    async def test_create_task_blocks_on_backlog_dependency(self, client, fake_redis, make_task):
+        """POST /api/tasks: a dependency still in the backlog blocks the new task."""
+        from src.engine.sts import ShortTermScheduler
+
+        make_task(task_id="check", status=TaskStatus.BACKLOG).to_redis(fake_redis)
+        sts = ShortTermScheduler()
+        with patch("src.server._sts", sts):
+            resp = await client.post("/api/tasks", json={
+                "title": "Pay rent", "priority": 0, "depends_on": ["check"],
+            })
+        assert resp.status_code == 200
+        assert sts.total_count == 1
+        assert sts.dequeue(energy_level=5) is None
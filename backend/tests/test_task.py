@@ -52,6 +52,47 @@ class TestTaskDefaults:
         assert task.tags == ["backend", "hotfix"]
 
 
+class TestTaskClamping:
+    def test_energy_and_cognitive_clamped_high(self):
+        task = Task(task_id="c1", title="Too much", energy_cost=99, cognitive_load=12)
+        assert task.energy_cost == 5
+        assert task.cognitive_load == 5
+
+    def test_energy_and_cognitive_clamped_low(self):
+        task = Task(task_id="c2", title="Too little", energy_cost=0, cognitive_load=-3)
+        assert task.energy_cost == 1
+        assert task.cognitive_load == 1
+
+    def test_duration_clamped_to_one_minute(self):
+        task = Task(task_id="c3", title="Instant", estimated_duration=0)
+        assert task.estimated_duration == 1
+
+    def test_priority_clamped_to_known_levels(self):
+        assert Task(task_id="c4", title="Low", priority=9).priority == Priority.P3_BACKGROUND
+        assert Task(task_id="c5", title="High", priority=-1).priority == Priority.P0_URGENT
+
+    def test_energy_cost_pct_clamped(self):
+        assert Task(task_id="c6", title="Pct", energy_cost_pct=150.0).energy_cost_pct == 100.0
+
+    def test_energy_cost_follows_pct(self):
+        """A percent cost sets the coarse cost to the smallest covering level."""
+        assert Task(task_id="c9", title="Pct", energy_cost=1, energy_cost_pct=65.0).energy_cost == 4
+        assert Task(task_id="c10", title="Pct", energy_cost=5, energy_cost_pct=60.0).energy_cost == 3
+        assert Task(task_id="c11", title="Pct", energy_cost=2).energy_cost == 2
+
+    def test_from_dict_clamps(self):
+        task = Task.from_dict({
+            "task_id": "c7", "title": "Stored", "energy_cost": "7", "tags": "[]",
+        })
+        assert task.energy_cost == 5
+
+    def test_in_range_values_untouched(self):
+        task = Task(task_id="c8", title="Fine", priority=Priority.P1_IMPORTANT,
+                    energy_cost=4, cognitive_load=2, estimated_duration=45)
+        assert task.priority is Priority.P1_IMPORTANT
+        assert (task.energy_cost, task.cognitive_load, task.estimated_duration) == (4, 2, 45)
+
+
 # ═══════════════════════════════════════════════════════════════════════════
 # Deadline Urgency Scoring
 # ═══════════════════════════════════════════════════════════════════════════
@@ -227,6 +268,29 @@ class TestSerialization:
         assert task.estimated_duration == 45
         assert task.cognitive_load == 4
 
+    def test_depends_on_json_roundtrip(self):
+        task = Task(task_id="dep", title="Pay card", depends_on=["check-balance"])
+        d = task.to_dict()
+        assert isinstance(d["depends_on"], str)
+        assert Task.from_dict(d).depends_on == ["check-balance"]
+
+    def test_depends_on_defaults_empty_for_old_records(self):
+        """Records stored before depends_on existed load with no dependencies."""
+        task = Task.from_dict({"task_id": "old", "title": "Old", "tags": "[]"})
+        assert task.depends_on == []
+
+    def test_energy_cost_pct_roundtrip(self):
+        """Finer energy cost survives the string round-trip through Redis."""
+        task = Task(task_id="pct", title="Pct", energy_cost=4, energy_cost_pct=65.0)
+        d = {k: str(v) for k, v in task.to_dict().items()}
+        restored = Task.from_dict(d)
+        assert restored.energy_cost_pct == 65.0
+        assert restored.energy_pct == 65.0
+
+    def test_energy_pct_derives_from_level(self):
+        task = Task(task_id="lvl", title="Level", energy_cost=3)
+        assert task.energy_pct == 60
+
     def test_tags_json_roundtrip(self):
         """Tags list survives json.dumps / json.loads cycle (as Redis would do)."""
         tags = ["backend", "urgent", "p0"]